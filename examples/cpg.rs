@@ -0,0 +1,35 @@
+//! A two-neuron central pattern generator.
+//!
+//! Uses the oscillator parameters from Beer (1995), "On the dynamics of small continuous-time
+//! recurrent neural networks": both neurons excite themselves, one excites the other, and the
+//! other inhibits back, producing a stable limit cycle with a period of roughly 29 time units.
+//!
+//! Run with `cargo run --example cpg > cpg.csv` to get the output trajectory as CSV.
+
+use ctrnn::RLCTRNN;
+
+fn main() {
+    let mut ctrnn = RLCTRNN::new(2);
+    ctrnn
+        .set_weight(0, 0, 4.5)
+        .set_weight(1, 1, 4.5)
+        .set_weight(0, 1, -1.0)
+        .set_weight(1, 0, 1.0)
+        .set_bias(0, -2.75)
+        .set_bias(1, -1.75);
+
+    // Hold every parameter at its center so the network behaves as a plain CTRNN
+    let params = ctrnn.biases.iter_mut()
+        .chain(ctrnn.time_constants.iter_mut())
+        .chain(ctrnn.weights.iter_mut().flatten());
+    for param in params { param.amplitude = 0.0; }
+
+    let dt = 0.05;
+    let mut voltages = ctrnn.init_voltage();
+    println!("time,output_0,output_1");
+    for step in 0..2000 {
+        let outputs = ctrnn.get_outputs(&voltages);
+        println!("{:.2},{:.6},{:.6}", step as f64 * dt, outputs[0], outputs[1]);
+        voltages = ctrnn.update(dt, &voltages, vec![]);
+    }
+}
@@ -1,11 +1,28 @@
 pub type ActivationFunc = fn(f64) -> f64;
 
 /// A logistic curve that tends towards `0` for negative inputs and `1` for positive inputs.
+/// > Only ever exponentiates a non-positive number, so it stays accurate for extreme inputs
 pub fn sigmoid(x: f64) -> f64 {
-    (1.0 + (-x).exp()).recip()
+    #[cfg(test)]
+    test::SIGMOID_CALLS.with(|calls| calls.set(calls.get() + 1));
+    if x >= 0.0 {
+        (1.0 + (-x).exp()).recip()
+    } else {
+        let e = x.exp();
+        e / (1.0 + e)
+    }
 }
 
+/// A [`sigmoid`] whose transition is centered on `midpoint` and scaled by `steepness`.
+/// > `sigmoid_param(x, 1.0, 0.0)` is the plain [`sigmoid`]
+pub fn sigmoid_param(x: f64, steepness: f64, midpoint: f64) -> f64 {
+    sigmoid(steepness * (x - midpoint))
+}
+
+/// The inverse of [`sigmoid`].
+/// > The input is clamped to `[EPSILON, 1 - EPSILON]`, so `0` and `1` give large but finite results
 pub fn inverse_sigmoid(x: f64) -> f64 {
+    let x = x.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
     (x / (1.0 - x)).ln()
 }
 
@@ -13,3 +30,43 @@ pub fn inverse_sigmoid(x: f64) -> f64 {
 pub fn relu(x: f64) -> f64 {
     x.max(0.0)
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        /// How many times [`sigmoid`] ran on this thread, so tests can check how much work a step does.
+        pub(crate) static SIGMOID_CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[test]
+    fn extreme_sigmoid() {
+        assert_eq!(sigmoid(1000.0), 1.0);
+        assert_eq!(sigmoid(-1000.0), 0.0);
+        assert_eq!(sigmoid(0.0), 0.5);
+        assert!((sigmoid(-40.0) - (-40.0f64).exp()).abs() < 1e-30);
+        assert!((sigmoid(-2.0) + sigmoid(2.0) - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn bounded_inverse_sigmoid() {
+        assert!(inverse_sigmoid(0.0).is_finite() && inverse_sigmoid(0.0) < -30.0);
+        assert!(inverse_sigmoid(1.0).is_finite() && inverse_sigmoid(1.0) > 30.0);
+        assert!(inverse_sigmoid(1e-300).is_finite());
+        for x in [-5.0, -0.3, 0.0, 1.7, 12.0] {
+            assert!((inverse_sigmoid(sigmoid(x)) - x).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn parameterized_sigmoid() {
+        for x in [-3.0, -0.5, 0.0, 0.25, 4.0] {
+            assert_eq!(sigmoid_param(x, 1.0, 0.0), sigmoid(x));
+        }
+        assert_eq!(sigmoid_param(2.0, 1.0, 2.0), 0.5);
+        assert!(sigmoid_param(2.5, 4.0, 2.0) > sigmoid_param(2.5, 1.0, 2.0));
+        assert!(sigmoid_param(1.5, 4.0, 2.0) < sigmoid_param(1.5, 1.0, 2.0));
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fmt, ops::Range};
+
+/// Errors returned by the fallible (`try_*`, `from_*`, ...) parts of the API.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CtrnnError {
+    /// A node index was not within `0..count`.
+    NodeOutOfRange { index: usize, count: usize },
+    /// A vector or matrix did not have the expected length.
+    DimensionMismatch { expected: usize, found: usize },
+    /// A time constant was zero, negative, or not finite.
+    InvalidTimeConstant { node: usize, value: f64 },
+    /// A parameter or state of a node was `NaN` or infinite.
+    NonFinite { node: usize, value: f64 },
+    /// A parameter of a node was outside the range learning keeps it within.
+    OutOfBounds { node: usize, value: f64, range: Range<f64> },
+    /// Input could not be parsed into a network.
+    ParseError(String),
+}
+
+impl fmt::Display for CtrnnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NodeOutOfRange { index, count } =>
+                write!(f, "node index {index} out of range for network of size {count}"),
+            Self::DimensionMismatch { expected, found } =>
+                write!(f, "expected {expected} values, found {found}"),
+            Self::InvalidTimeConstant { node, value } =>
+                write!(f, "time constant {value} of node {node} must be positive and finite"),
+            Self::NonFinite { node, value } =>
+                write!(f, "node {node} has non-finite value {value}"),
+            Self::OutOfBounds { node, value, range } =>
+                write!(f, "value {value} of node {node} is outside {range:?}"),
+            Self::ParseError(message) =>
+                write!(f, "failed to parse network: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CtrnnError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let message = CtrnnError::NodeOutOfRange { index: 7, count: 3 }.to_string();
+        assert_eq!(message, "node index 7 out of range for network of size 3");
+
+        let message = CtrnnError::DimensionMismatch { expected: 9, found: 4 }.to_string();
+        assert!(message.contains('9') && message.contains('4'));
+
+        let message = CtrnnError::InvalidTimeConstant { node: 2, value: -0.5 }.to_string();
+        assert!(message.contains("node 2") && message.contains("-0.5"));
+
+        let message = CtrnnError::NonFinite { node: 5, value: f64::NAN }.to_string();
+        assert!(message.contains("node 5") && message.contains("NaN"));
+
+        let message = CtrnnError::OutOfBounds { node: 1, value: 20.0, range: -16.0..16.0 }.to_string();
+        assert_eq!(message, "value 20 of node 1 is outside -16.0..16.0");
+
+        let message = CtrnnError::ParseError("unexpected end of input".into()).to_string();
+        assert!(message.contains("unexpected end of input"));
+    }
+}
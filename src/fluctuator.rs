@@ -4,6 +4,7 @@ use rand::prelude::*;
 #[derive(Clone)]
 pub struct Fluctuator {
     pub center: f64,
+    /// The bounds [`update`](Self::update) keeps the center within.
     pub range: Range<f64>,
     pub range_period: Range<f64>,
     pub range_amplitude: Range<f64>,
@@ -14,41 +15,92 @@ pub struct Fluctuator {
 
     pub convergence_rate: f64,
     pub learning_rate: f64,
+    /// Whether [`update`](Self::update) moves the center.
+    /// > The fluctuation itself keeps going either way
+    pub trainable: bool,
 }
 
 impl Fluctuator {
+    /// Create a fluctuator around `center` with a random period.
+    /// > The amplitude starts a tenth of the way into `range_amplitude` (about `1.0` by default)
     pub fn new(center: f64) -> Self {
+        Self::with_rng(center, &mut rand::thread_rng())
+    }
+
+    /// Same as [`new`](Self::new), but draws the random period from `rng`.
+    pub fn with_rng(center: f64, rng: &mut impl Rng) -> Self {
         let mut flux = Self {
             center,
             ..Default::default()
         };
-        flux.randomize_period();
+        let span = flux.range_amplitude.end - flux.range_amplitude.start;
+        flux.amplitude = flux.range_amplitude.start + span / 10.0;
+        flux.randomize_period(rng);
         flux
     }
 
     pub fn get(&self) -> f64 {
-        let theta = self.time * 2.0 * PI / self.period;
-        self.center + self.amplitude * theta.sin()
+        self.center + self.amplitude * self.wave()
+    }
+
+    /// The current phase of the fluctuation, in `-1.0..=1.0`.
+    /// > A zero period doesn't fluctuate, instead of dividing by zero
+    fn wave(&self) -> f64 {
+        if self.period <= 0.0 { return 0.0; }
+        (self.time * 2.0 * PI / self.period).sin()
+    }
+
+    /// Replace the bounds of the center, clamping the current center into them.
+    pub fn set_range(&mut self, range: Range<f64>) -> &mut Self {
+        self.range = range;
+        self.center = self.range.clamp(self.center);
+        self
     }
 
-    fn randomize_period(&mut self) {
+    /// Replace the amplitude and period ranges, pulling the current amplitude and period into them.
+    /// > A new period is drawn from `rng` if the current one falls outside the new range. Panics if
+    /// > either range is empty, or if `period` starts below `0.1`, since periods are drawn in steps
+    /// > of `0.1` and would otherwise round down to zero
+    pub fn set_ranges(&mut self, amplitude: Range<f64>, period: Range<f64>, rng: &mut impl Rng) -> &mut Self {
+        assert!(!amplitude.is_empty(), "amplitude range {amplitude:?} is empty");
+        assert!(period.start >= 0.1 && !period.is_empty(), "period range {period:?} must be non-empty and start at 0.1 or above");
+        self.range_amplitude = amplitude;
+        self.amplitude = self.range_amplitude.clamp(self.amplitude);
+        self.range_period = period;
+        if !self.range_period.contains(&self.period) { self.randomize_period(rng); }
+        self
+    }
+
+    fn randomize_period(&mut self, rng: &mut impl Rng) {
         let diff = self.range_period.end - self.range_period.start;
-        let mut rng = rand::thread_rng();
         let p = self.range_period.start + diff * rng.gen::<f64>();
         self.period = (p * 10.0).floor() / 10.0;
         self.time = 0.0;
     }
 
     pub fn update(&mut self, dt: f64, reward: f64) -> f64 {
-        self.amplitude -= self.convergence_rate * self.range_amplitude.end * reward;
+        self.update_with_rate(dt, reward, self.convergence_rate, &mut rand::thread_rng())
+    }
+
+    /// Same as [`update`](Self::update), but shrinks the amplitude using `convergence_rate`
+    /// instead of the fluctuator's own, and draws the next period from `rng`.
+    pub fn update_with_rate(&mut self, dt: f64, reward: f64, convergence_rate: f64, rng: &mut impl Rng) -> f64 {
+        self.amplitude -= convergence_rate * self.range_amplitude.end * reward;
         self.amplitude = self.range_amplitude.clamp(self.amplitude);
 
-        let theta = self.time * 2.0 * PI / self.period;
-        let d = self.amplitude * theta.sin();
-        self.center += self.learning_rate * d * reward;
+        let d = self.amplitude * self.wave();
+        if self.trainable {
+            self.center += self.learning_rate * d * reward;
+            self.center = self.range.clamp(self.center);
+        }
 
+        // Carry the overshoot into the next period so the phase doesn't jump back to zero
         self.time += dt;
-        if self.time > self.period { self.randomize_period(); }
+        if self.time >= self.period {
+            let carry = self.time - self.period;
+            self.randomize_period(rng);
+            self.time = if self.period > 0.0 { carry % self.period } else { 0.0 };
+        }
         d
     }
 }
@@ -65,12 +117,13 @@ impl Default for Fluctuator {
             time: 0.0,
             convergence_rate: 0.1,
             learning_rate: 0.1,
+            trainable: true,
         }
     }
 }
 
-impl Into<f64> for Fluctuator {
-    fn into(self) -> f64 { self.get() }
+impl From<Fluctuator> for f64 {
+    fn from(value: Fluctuator) -> Self { value.get() }
 }
 
 impl From<f64> for Fluctuator {
@@ -86,3 +139,41 @@ impl Clamp<f64> for Range<f64> {
         self.start.max(self.end.min(value))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn creation() {
+        let flux = Fluctuator::new(2.0);
+        assert!(flux.amplitude > 0.0 && flux.amplitude < flux.range_amplitude.end);
+        assert!(flux.period >= flux.range_period.start && flux.period < flux.range_period.end);
+        assert_eq!(flux.get(), 2.0);
+
+        let quarter = Fluctuator { time: flux.period / 4.0, ..flux };
+        assert!((quarter.get() - (2.0 + quarter.amplitude)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn continuous_phase() {
+        let mut flux = Fluctuator { range_period: 4.0..4.0, period: 4.0, amplitude: 1.0, ..Default::default() };
+        for step in 1..=100 {
+            flux.update(0.3, 0.0);
+            let expected = (step as f64 * 0.3 * 2.0 * PI / 4.0).sin();
+            assert!((flux.get() - expected).abs() < 1e-9, "phase jumped at step {step}");
+            assert!(flux.time < flux.period);
+        }
+    }
+
+    #[test]
+    fn zero_period() {
+        let mut flux = Fluctuator { range_period: 0.0..0.05, amplitude: 1.0, ..Default::default() };
+        for _ in 0..10 {
+            flux.update(0.1, 0.5);
+            assert_eq!(flux.period, 0.0);
+            assert!(flux.time.is_finite() && flux.get().is_finite());
+        }
+        assert_eq!(flux.get(), flux.center);
+    }
+}
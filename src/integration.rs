@@ -0,0 +1,25 @@
+/// The numerical method used to advance voltages by one step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntegrationMethod {
+    /// Forward Euler, one derivative evaluation per step.
+    #[default]
+    Euler,
+    /// Heun's method (explicit trapezoidal), two derivative evaluations per step.
+    Heun,
+    /// Classic fourth-order Runge-Kutta, four derivative evaluations per step.
+    RungeKutta4,
+}
+
+impl IntegrationMethod {
+    /// The largest `dt / tau` for which one step is a weighted average of the current voltage and
+    /// the targets sampled at each stage, so a node can't overshoot the range of its targets.
+    /// > Past this, steps stay stable for a lone linear node but can overshoot badly once nodes
+    /// > are coupled through the sigmoid
+    pub fn max_step_ratio(self) -> f64 {
+        match self {
+            Self::Euler => 1.0,
+            Self::Heun => 1.0,
+            Self::RungeKutta4 => 1.29,
+        }
+    }
+}
@@ -1,8 +1,37 @@
+//! A continuous time recurrent neural network (CTRNN) library.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ctrnn::{Fluctuator, RLCTRNN};
+//!
+//! let mut ctrnn = RLCTRNN::new(3);
+//! ctrnn.set_weight(0, 1, 2.0).set_weight(1, 2, 1.5).set_bias(2, -1.0);
+//!
+//! let mut voltages = ctrnn.init_voltage();
+//! for _ in 0..10 {
+//!     voltages = ctrnn.update(0.1, &voltages, vec![0.5]);
+//! }
+//! let outputs = ctrnn.get_outputs(&voltages);
+//! assert_eq!(outputs.len(), 3);
+//! assert!(outputs.iter().all(|output| (0.0..=1.0).contains(output)));
+//!
+//! let bias: &Fluctuator = &ctrnn.biases[2];
+//! assert_eq!(bias.center, -1.0);
+//! ```
+
 pub mod node;
 pub mod activation;
+pub mod error;
 pub mod fluctuator;
+pub mod integration;
+pub mod reward;
 // pub mod ctrnn;
 pub mod rlctrnn;
 
 // pub use ctrnn::*;
+pub use error::CtrnnError;
+pub use fluctuator::Fluctuator;
+pub use integration::IntegrationMethod;
+pub use reward::Reward;
 pub use rlctrnn::*;
@@ -11,3 +11,28 @@ impl Node {
 }
 
 impl Default for Node { fn default() -> Self { Self::new(0.0, 1.0) } }
+
+/// The source end of a connection, i.e. the node a weight reads from.
+///
+/// Paired with [`To`] so the direction of a connection is checked by the compiler:
+///
+/// ```compile_fail
+/// use ctrnn::{RLCTRNN, node::{From, To}};
+///
+/// let mut ctrnn = RLCTRNN::new(3);
+/// ctrnn.set_weight(To(2), From(0), 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct From(pub usize);
+
+/// The destination end of a connection, i.e. the node a weight excites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct To(pub usize);
+
+impl std::convert::From<usize> for From {
+    fn from(value: usize) -> Self { Self(value) }
+}
+
+impl std::convert::From<usize> for To {
+    fn from(value: usize) -> Self { Self(value) }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+/// The learning signal given to an [`RLCTRNN`](crate::RLCTRNN).
+///
+/// Either a single scalar, or several named objectives (e.g. speed vs. energy) that are collapsed
+/// into a scalar using per-component weights.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reward {
+    Scalar(f64),
+    Components(Vec<(String, f64)>),
+}
+
+impl Reward {
+    /// Create a reward made of a single named component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ctrnn::Reward;
+    ///
+    /// let reward = Reward::component("speed", 0.8).with("energy", -0.2);
+    /// ```
+    pub fn component(name: &str, value: f64) -> Self {
+        Self::Components(vec![(name.to_string(), value)])
+    }
+
+    /// Add another named component to this reward.
+    /// > A scalar reward is kept as an unnamed component with weight `1.0`
+    pub fn with(self, name: &str, value: f64) -> Self {
+        let mut components = match self {
+            Self::Scalar(value) => vec![(String::new(), value)],
+            Self::Components(components) => components,
+        };
+        components.push((name.to_string(), value));
+        Self::Components(components)
+    }
+
+    /// Collapse the reward into the scalar used by [`Fluctuator::update`](crate::fluctuator::Fluctuator::update).
+    /// > Components without an entry in `weights` are weighted `1.0`
+    pub fn value(&self, weights: &HashMap<String, f64>) -> f64 {
+        match self {
+            Self::Scalar(value) => *value,
+            Self::Components(components) => components.iter()
+                .map(|(name, value)| weights.get(name).unwrap_or(&1.0) * value)
+                .sum(),
+        }
+    }
+}
+
+impl From<f64> for Reward {
+    fn from(value: f64) -> Self { Self::Scalar(value) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn collapse() {
+        let mut weights = HashMap::new();
+        let reward = Reward::component("speed", 0.5).with("energy", 0.25);
+        assert_eq!(reward.value(&weights), 0.75);
+        assert_eq!(Reward::from(0.75).value(&weights), 0.75);
+
+        weights.insert("energy".to_string(), -2.0);
+        assert_eq!(reward.value(&weights), 0.0);
+    }
+}
@@ -1,10 +1,75 @@
-use crate::{fluctuator::Fluctuator, activation::sigmoid};
+use std::{collections::{HashMap, VecDeque}, ops::Range};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use crate::{fluctuator::Fluctuator, activation::sigmoid, node::{From as Src, To}, reward::Reward, error::CtrnnError, integration::IntegrationMethod};
 
+/// Adaptive amplitude annealing for [`RLCTRNN::learn`].
+///
+/// Each fluctuator's convergence rate is divided by `1 + sensitivity * variance`, where `variance`
+/// is taken over the last `window` rewards, so amplitudes keep shrinking at the configured rate
+/// while rewards are consistent (exploitation) but stay large while they are noisy (exploration).
+#[derive(Clone)]
+pub struct Annealing {
+    pub window: usize,
+    pub sensitivity: f64,
+    rewards: VecDeque<f64>,
+}
+
+impl Annealing {
+    pub fn new(window: usize, sensitivity: f64) -> Self {
+        Self { window, sensitivity, rewards: VecDeque::with_capacity(window) }
+    }
+
+    /// Record a reward and return the factor to scale convergence rates by.
+    fn observe(&mut self, reward: f64) -> f64 {
+        if self.rewards.len() >= self.window { self.rewards.pop_front(); }
+        self.rewards.push_back(reward);
+
+        let n = self.rewards.len() as f64;
+        let mean = self.rewards.iter().sum::<f64>() / n;
+        let variance = self.rewards.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+        (1.0 + self.sensitivity * variance).recip()
+    }
+}
+
+/// A CTRNN whose parameters are [`Fluctuator`]s, learning through reward-modulated oscillation.
+///
+/// # Thread safety
+///
+/// `RLCTRNN` is `Send + Sync`, so many networks can be evaluated in parallel (e.g. one per thread
+/// in a fitness evaluation). This is checked at compile time, so any new field must keep it true.
+///
+/// # Invariants
+///
+/// `biases`, `time_constants`, `voltages`, and every row of `weights` have `count` entries, and
+/// `weights` has `count` rows. The fields are public, so this can be broken from outside, but in
+/// debug builds every mutating method asserts it before returning.
+#[derive(Clone)]
 pub struct RLCTRNN {
     pub count: usize,
     pub biases: Vec<Fluctuator>,
     pub time_constants: Vec<Fluctuator>,
-    pub weights: Vec<Vec<Fluctuator>>
+    pub weights: Vec<Vec<Fluctuator>>,
+    pub reward_weights: HashMap<String, f64>,
+    /// The voltages used by [`tick`](Self::tick) and [`output`](Self::output).
+    pub voltages: Vec<f64>,
+    pub annealing: Option<Annealing>,
+    /// The random stream every fluctuator draws its periods from.
+    pub rng: StdRng,
+    /// How [`update`](Self::update) and [`tick`](Self::tick) integrate the voltages.
+    /// > Fluctuators only move in [`learn`](Self::learn), so every stage of a step sees the same
+    /// > parameter values. Effective time constants are floored at
+    /// > `dt / integration.max_step_ratio()`, i.e. `dt` for Euler and Heun, and `dt / 1.29` for RK4
+    pub integration: IntegrationMethod,
+    /// Whether [`tick`](Self::tick) replaces a non-finite voltage with the node's previous one
+    /// (or `0.0` if that wasn't finite either), counting each replacement in
+    /// [`nan_events`](Self::nan_events).
+    /// > Off by default, so instability shows up as `NaN` instead of being hidden
+    pub sanitize: bool,
+    nan_events: usize,
+    activations: Vec<f64>,
+    slopes: [Vec<f64>; 3],
+    stage: Vec<f64>,
+    next_voltages: Vec<f64>,
 }
 
 impl RLCTRNN {
@@ -19,18 +84,52 @@ impl RLCTRNN {
     /// ```
     /// > This will create a fully-connected RLCTRNN with 6 nodes
     pub fn new(nodes: usize) -> Self {
+        Self::from_rng(nodes, StdRng::from_entropy())
+    }
+
+    /// Create a new RLCTRNN whose randomness is fully determined by `seed`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut a = ctrnn::RLCTRNN::with_seed(6, 42);
+    /// let mut b = ctrnn::RLCTRNN::with_seed(6, 42);
+    /// for _ in 0..100 {
+    ///     a.tick(&[1.0], 0.1).learn(0.1, 0.5);
+    ///     b.tick(&[1.0], 0.1).learn(0.1, 0.5);
+    /// }
+    /// assert_eq!(a.output(), b.output());
+    /// ```
+    pub fn with_seed(nodes: usize, seed: u64) -> Self {
+        Self::from_rng(nodes, StdRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(nodes: usize, rng: StdRng) -> Self {
         let mut ctrnn = Self {
             count: nodes,
             biases: vec![],
             time_constants: vec![],
             weights: vec![],
+            reward_weights: HashMap::new(),
+            voltages: vec![0.0; nodes],
+            annealing: None,
+            rng,
+            integration: IntegrationMethod::default(),
+            sanitize: false,
+            nan_events: 0,
+            activations: vec![],
+            slopes: Default::default(),
+            stage: vec![],
+            next_voltages: vec![],
         };
 
+        // Like `add_node`, only weights fluctuate initially, so a time constant can't swing through zero
+        let still = |center, rng: &mut StdRng| Fluctuator { amplitude: 0.0, ..Fluctuator::with_rng(center, rng) };
         for _ in 0..nodes {
-            ctrnn.biases.push(Fluctuator::from(0.0));
-            ctrnn.time_constants.push(Fluctuator::from(1.0));
+            ctrnn.biases.push(still(0.0, &mut ctrnn.rng));
+            ctrnn.time_constants.push(still(1.0, &mut ctrnn.rng));
             let mut weights = vec![];
-            for _ in 0..nodes { weights.push(Fluctuator::from(0.0)); }
+            for _ in 0..nodes { weights.push(Fluctuator::with_rng(0.0, &mut ctrnn.rng)); }
             ctrnn.weights.push(weights);
         }
 
@@ -46,9 +145,12 @@ impl RLCTRNN {
     /// let mut ctrnn = ctrnn::RLCTRNN::new(6);
     /// ctrnn.set_bias(0, 1.1);
     /// ```
-    /// > This will cause node `0` to have a slight dampening effect
+    /// > This will cause node `0` to have a slight dampening effect. Learning keeps the center within
+    /// > the bias' range, see [`set_bias_range`](Self::set_bias_range)
     pub fn set_bias(&mut self, index: usize, value: f64) -> &mut Self {
+        self.check_node(index);
         self.biases[index].center = value;
+        self.debug_assert_invariants();
         self
     }
 
@@ -63,7 +165,9 @@ impl RLCTRNN {
     /// ```
     /// > This will cause node `0` to have a slight dampening effect
     pub fn set_time_constant(&mut self, index: usize, value: f64) -> &mut Self {
+        self.check_node(index);
         self.time_constants[index].center = value;
+        self.debug_assert_invariants();
         self
     }
 
@@ -77,53 +181,565 @@ impl RLCTRNN {
     /// ctrnn.set_weight(0, 2, 1.0);
     /// ctrnn.set_weight(1, 2, 1.0);
     /// ```
-    /// > This will make it so node `2` gets excited from nodes `0` and `1`
-    pub fn set_weight(&mut self, from: usize, to: usize, value: f64) -> &mut Self {
+    /// > This will make it so node `2` gets excited from nodes `0` and `1`. Learning keeps the center
+    /// > within the weight's range, see [`set_weight_range`](Self::set_weight_range), so a value set
+    /// > outside it is clamped on the next [`learn`](Self::learn)
+    ///
+    /// The ends can also be given as [`From`](crate::node::From) and [`To`], so they cannot be swapped by accident:
+    ///
+    /// ```rust
+    /// use ctrnn::node::{From, To};
+    ///
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// ctrnn.set_weight(From(0), To(2), 1.0);
+    /// ```
+    pub fn set_weight(&mut self, from: impl Into<Src>, to: impl Into<To>, value: f64) -> &mut Self {
+        let (Src(from), To(to)) = (from.into(), to.into());
+        self.check_node(from);
+        self.check_node(to);
         self.weights[to][from].center = value;
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// Fallible version of [`set_bias`](Self::set_bias).
+    /// > Fails if `index` is out of range, or `value` is not finite or outside the bias' range
+    pub fn try_set_bias(&mut self, index: usize, value: f64) -> Result<&mut Self, CtrnnError> {
+        self.try_node(index)?;
+        if !value.is_finite() { return Err(CtrnnError::NonFinite { node: index, value }); }
+        Self::try_within(&self.biases[index], index, value)?;
+        Ok(self.set_bias(index, value))
+    }
+
+    /// Fallible version of [`set_time_constant`](Self::set_time_constant).
+    /// > Fails if `index` is out of range, or `value` is not positive and finite or outside the time
+    /// > constant's range
+    pub fn try_set_time_constant(&mut self, index: usize, value: f64) -> Result<&mut Self, CtrnnError> {
+        self.try_node(index)?;
+        if !(value.is_finite() && value > 0.0) {
+            return Err(CtrnnError::InvalidTimeConstant { node: index, value });
+        }
+        Self::try_within(&self.time_constants[index], index, value)?;
+        Ok(self.set_time_constant(index, value))
+    }
+
+    /// Fallible version of [`set_weight`](Self::set_weight).
+    /// > Fails if either end is out of range, or `value` is not finite or outside the weight's range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// assert!(ctrnn.try_set_weight(0, 2, 1.0).is_ok());
+    /// assert!(ctrnn.try_set_weight(0, 3, 1.0).is_err());
+    /// ```
+    pub fn try_set_weight(&mut self, from: impl Into<Src>, to: impl Into<To>, value: f64) -> Result<&mut Self, CtrnnError> {
+        let (Src(from), To(to)) = (from.into(), to.into());
+        self.try_node(from)?;
+        self.try_node(to)?;
+        if !value.is_finite() { return Err(CtrnnError::NonFinite { node: to, value }); }
+        Self::try_within(&self.weights[to][from], to, value)?;
+        Ok(self.set_weight(from, to, value))
+    }
+
+    /// The center of a node's bias.
+    pub fn bias(&self, index: usize) -> f64 {
+        self.check_node(index);
+        self.biases[index].center
+    }
+
+    /// The center of a node's time constant.
+    pub fn time_constant(&self, index: usize) -> f64 {
+        self.check_node(index);
+        self.time_constants[index].center
+    }
+
+    /// The center of the weight from one node to another.
+    pub fn weight(&self, from: impl Into<Src>, to: impl Into<To>) -> f64 {
+        let (Src(from), To(to)) = (from.into(), to.into());
+        self.check_node(from);
+        self.check_node(to);
+        self.weights[to][from].center
+    }
+
+    /// Set every weight center to `0.0`, leaving biases, time constants, and voltages untouched.
+    /// > Weights keep fluctuating with their current amplitudes
+    pub fn clear_weights(&mut self) -> &mut Self {
+        for weight in self.weights.iter_mut().flatten() { weight.center = 0.0; }
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// The smallest and largest weight centers in the network.
+    /// > `(0.0, 0.0)` for a network without nodes
+    pub fn weight_bounds(&self) -> (f64, f64) {
+        if self.count == 0 { return (0.0, 0.0); }
+        self.weights.iter().flatten().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), weight| {
+            (min.min(weight.center), max.max(weight.center))
+        })
+    }
+
+    /// Linearly rescale every weight center from [`weight_bounds`](Self::weight_bounds) into `target`.
+    /// > If every weight is equal they all move to the middle of `target`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(2);
+    /// ctrnn.set_weight(0, 1, 4.0).set_weight(1, 0, -2.0).normalize_weights(0.0..1.0);
+    /// assert_eq!(ctrnn.weight_bounds(), (0.0, 1.0));
+    /// ```
+    pub fn normalize_weights(&mut self, target: Range<f64>) -> &mut Self {
+        let (min, max) = self.weight_bounds();
+        let span = target.end - target.start;
+        for weight in self.weights.iter_mut().flatten() {
+            weight.center = if max > min {
+                target.start + (weight.center - min) / (max - min) * span
+            } else {
+                target.start + span / 2.0
+            };
+        }
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// The nodes in `outputs` that no node in `inputs` reaches through non-zero weight centers.
+    /// > An input node always reaches itself
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(4);
+    /// ctrnn.set_weight(0, 1, 1.0).set_weight(1, 2, -1.0);
+    /// assert_eq!(ctrnn.unreachable_outputs(&[0], &[2, 3]), vec![3]);
+    /// ```
+    pub fn unreachable_outputs(&self, inputs: &[usize], outputs: &[usize]) -> Vec<usize> {
+        let mut reached = vec![false; self.count];
+        let mut queue: VecDeque<usize> = inputs.iter().copied().collect();
+        while let Some(from) = queue.pop_front() {
+            self.check_node(from);
+            if std::mem::replace(&mut reached[from], true) { continue; }
+            queue.extend((0..self.count).filter(|&to| !reached[to] && self.weights[to][from].center != 0.0));
+        }
+        outputs.iter().copied().filter(|&output| { self.check_node(output); !reached[output] }).collect()
+    }
+
+    /// Bound how far learning can move a weight.
+    /// > The center is clamped into `range` right away and on every [`learn`](Self::learn), by
+    /// > default `-16.0..16.0`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// ctrnn.set_weight_range(0, 2, -4.0..4.0);
+    /// ```
+    pub fn set_weight_range(&mut self, from: impl Into<Src>, to: impl Into<To>, range: Range<f64>) -> &mut Self {
+        let (Src(from), To(to)) = (from.into(), to.into());
+        self.check_node(from);
+        self.check_node(to);
+        self.weights[to][from].set_range(range);
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// Bound how far learning can move a bias.
+    /// > The center is clamped into `range` right away and on every [`learn`](Self::learn), by
+    /// > default `-16.0..16.0`
+    pub fn set_bias_range(&mut self, index: usize, range: Range<f64>) -> &mut Self {
+        self.check_node(index);
+        self.biases[index].set_range(range);
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// Freeze or unfreeze learning for an individual weight.
+    /// > A frozen weight keeps fluctuating around its center (and its amplitude still anneals),
+    /// > but [`learn`](Self::learn) never moves the center. Set its `amplitude` to `0.0` as well
+    /// > to hold it exactly at its center.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// ctrnn.set_weight(0, 2, 1.0).set_weight_trainable(0, 2, false);
+    /// ```
+    pub fn set_weight_trainable(&mut self, from: impl Into<Src>, to: impl Into<To>, trainable: bool) -> &mut Self {
+        let (Src(from), To(to)) = (from.into(), to.into());
+        self.check_node(from);
+        self.check_node(to);
+        self.weights[to][from].trainable = trainable;
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// Set how much a named [`Reward`] component contributes to learning.
+    /// > Components that were never weighted count with `1.0`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ctrnn::Reward;
+    ///
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// ctrnn.set_reward_weight("energy", 0.5);
+    /// ctrnn.learn(0.1, Reward::component("speed", 1.0).with("energy", -0.4));
+    /// ```
+    pub fn set_reward_weight(&mut self, name: &str, weight: f64) -> &mut Self {
+        self.reward_weights.insert(name.to_string(), weight);
+        self
+    }
+
+    /// Advance every bias, time constant, and weight fluctuator by `dt`, moving their centers
+    /// towards the current fluctuation in proportion to `reward`.
+    /// > With [`Annealing`] enabled, amplitudes shrink more slowly while rewards are noisy
+    pub fn learn(&mut self, dt: f64, reward: impl Into<Reward>) -> &mut Self {
+        let reward = reward.into().value(&self.reward_weights);
+        let scale = self.annealing.as_mut().map_or(1.0, |annealing| annealing.observe(reward));
+        let (params, rng) = self.params_mut();
+        for param in params {
+            let rate = param.convergence_rate * scale;
+            param.update_with_rate(dt, reward, rate, rng);
+        }
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// Enable [`Annealing`] over the last `window` rewards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// ctrnn.enable_annealing(20, 10.0);
+    /// ```
+    pub fn enable_annealing(&mut self, window: usize, sensitivity: f64) -> &mut Self {
+        self.annealing = Some(Annealing::new(window, sensitivity));
+        self
+    }
+
+    /// Apply the same fluctuation and learning settings to every bias, time constant, and weight.
+    /// > Panics on the ranges [`Fluctuator::set_ranges`] rejects
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(6);
+    /// ctrnn.configure(0.01..2.0, 4.0..8.0, 0.05, 0.2);
+    /// ```
+    pub fn configure(
+        &mut self,
+        amplitude_range: Range<f64>,
+        period_range: Range<f64>,
+        learning_rate: f64,
+        convergence_rate: f64,
+    ) -> &mut Self {
+        let (params, rng) = self.params_mut();
+        for param in params {
+            param.set_ranges(amplitude_range.clone(), period_range.clone(), rng);
+            param.learning_rate = learning_rate;
+            param.convergence_rate = convergence_rate;
+        }
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// Draw every time constant's center from `range`, uniformly or, with `log_scale`, uniformly
+    /// on a log axis so fast and slow nodes are equally common.
+    /// > Uses its own random stream seeded by `seed`, and panics unless `range` is non-empty and
+    /// > strictly positive. Learning still clamps the centers into each time constant's own range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(6);
+    /// ctrnn.randomize_time_constants(0.1..10.0, true, 42);
+    /// ```
+    pub fn randomize_time_constants(&mut self, range: Range<f64>, log_scale: bool, seed: u64) -> &mut Self {
+        assert!(range.start > 0.0 && !range.is_empty(), "time constant range {range:?} must be non-empty and positive");
+        let mut rng = StdRng::seed_from_u64(seed);
+        for tau in self.time_constants.iter_mut() {
+            tau.center = if log_scale {
+                rng.gen_range(range.start.ln()..range.end.ln()).exp().clamp(range.start, range.end)
+            } else {
+                rng.gen_range(range.clone())
+            };
+        }
+        self.debug_assert_invariants();
         self
     }
 
     pub fn add_node(&mut self) -> &mut Self {
-        fn flux(center: f64, amplitude: f64) -> Fluctuator {
-            let mut f = Fluctuator::new(center);
+        fn flux(center: f64, amplitude: f64, rng: &mut StdRng) -> Fluctuator {
+            let mut f = Fluctuator::with_rng(center, rng);
             f.amplitude = amplitude;
             f.range_period.start = 6.0;
             f
         }
-        self.biases.push(flux(0.0, 0.0));
-        self.time_constants.push(flux(1.0, 0.0));
-        let mut weights = vec![flux(0.0, 1.0)];
+        let rng = &mut self.rng;
+        self.biases.push(flux(0.0, 0.0, rng));
+        self.time_constants.push(flux(1.0, 0.0, rng));
+        let mut weights = vec![flux(0.0, 1.0, rng)];
         for i in 0..self.count {
-            self.weights[i].push(flux(0.0, 1.0));
-            weights.push(flux(0.0, 1.0));
+            self.weights[i].push(flux(0.0, 1.0, rng));
+            weights.push(flux(0.0, 1.0, rng));
         }
         self.weights.push(weights);
+        self.voltages.push(0.0);
         self.count += 1;
+        self.debug_assert_invariants();
         self
     }
 
-    pub fn update(&mut self, dt: f64, voltages: &Vec<f64>, inputs: Vec<f64>) -> Vec<f64> {
+    /// Advance `voltages` by `dt`, adding `inputs` to the first nodes afterwards.
+    /// > Effective time constants are floored at the smallest value the [`integration`](Self::integration)
+    /// > method can step without overshooting, so a node whose time constant fluctuates below it
+    /// > (or below zero) can't blow up or divide by zero
+    pub fn update(&mut self, dt: f64, voltages: &[f64], inputs: Vec<f64>) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.count);
+        self.update_into(dt, voltages, &inputs, &mut out);
+        out
+    }
+
+    /// Same as [`update`](Self::update), but writes the new voltages into `out` instead of
+    /// allocating a new `Vec`, so a simulation loop can reuse one buffer for every step.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// let mut voltages = ctrnn.init_voltage();
+    /// let mut next = vec![];
+    /// for _ in 0..10 {
+    ///     ctrnn.update_into(0.1, &voltages, &[0.5], &mut next);
+    ///     std::mem::swap(&mut voltages, &mut next);
+    /// }
+    /// ```
+    pub fn update_into(&mut self, dt: f64, voltages: &[f64], inputs: &[f64], out: &mut Vec<f64>) {
+        fn step(into: &mut Vec<f64>, from: &[f64], slope: &[f64], h: f64) {
+            into.clear();
+            into.extend(from.iter().zip(slope).map(|(v, k)| v + k * h));
+        }
+
+        let min_tau = (dt / self.integration.max_step_ratio()).max(f64::EPSILON);
+        let [mut k1, mut k2, mut k3] = std::mem::take(&mut self.slopes);
+        let mut stage = std::mem::take(&mut self.stage);
+        match self.integration {
+            IntegrationMethod::Euler => {
+                self.derivative(voltages, min_tau, out);
+                for i in 0..self.count { out[i] = voltages[i] + out[i] * dt; }
+            }
+            IntegrationMethod::Heun => {
+                self.derivative(voltages, min_tau, &mut k1);
+                step(&mut stage, voltages, &k1, dt);
+                self.derivative(&stage, min_tau, out);
+                for i in 0..self.count { out[i] = voltages[i] + (k1[i] + out[i]) * dt / 2.0; }
+            }
+            IntegrationMethod::RungeKutta4 => {
+                self.derivative(voltages, min_tau, &mut k1);
+                step(&mut stage, voltages, &k1, dt / 2.0);
+                self.derivative(&stage, min_tau, &mut k2);
+                step(&mut stage, voltages, &k2, dt / 2.0);
+                self.derivative(&stage, min_tau, &mut k3);
+                step(&mut stage, voltages, &k3, dt);
+                self.derivative(&stage, min_tau, out);
+                for i in 0..self.count {
+                    out[i] = voltages[i] + (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + out[i]) * dt / 6.0;
+                }
+            }
+        }
+        self.slopes = [k1, k2, k3];
+        self.stage = stage;
+        for (voltage, input) in out.iter_mut().zip(inputs) { *voltage += input; }
+    }
+
+    pub fn get_outputs(&self, voltages: &[f64]) -> Vec<f64> {
+        (0..self.count).map(|i| sigmoid(voltages[i] + self.biases[i].get())).collect()
+    }
+
+    /// Advance the network's own [`voltages`](Self::voltages) by `dt`.
+    /// > Equivalent to `voltages = update(dt, &voltages, inputs)` with an externally held vector
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// for _ in 0..10 { ctrnn.tick(&[0.5], 0.1); }
+    /// let outputs = ctrnn.output();
+    /// ```
+    pub fn tick(&mut self, inputs: &[f64], dt: f64) -> &mut Self {
+        let voltages = std::mem::take(&mut self.voltages);
+        let mut next = std::mem::take(&mut self.next_voltages);
+        self.update_into(dt, &voltages, inputs, &mut next);
+        if self.sanitize {
+            for (voltage, previous) in next.iter_mut().zip(&voltages) {
+                if voltage.is_finite() { continue; }
+                *voltage = if previous.is_finite() { *previous } else { 0.0 };
+                self.nan_events += 1;
+            }
+        }
+        self.voltages = next;
+        self.next_voltages = voltages;
+        self.debug_assert_invariants();
+        self
+    }
+
+    /// How many non-finite voltages [`tick`](Self::tick) has replaced while [`sanitize`](Self::sanitize) was on.
+    pub fn nan_events(&self) -> usize {
+        self.nan_events
+    }
+
+    /// [`tick`](Self::tick) `steps` times with `inputs` held constant, letting transients die out
+    /// before reading the (near-)steady response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// let outputs = ctrnn.settle(&[0.5], 0.1, 200).output();
+    /// ```
+    pub fn settle(&mut self, inputs: &[f64], dt: f64, steps: usize) -> &mut Self {
+        for _ in 0..steps { self.tick(inputs, dt); }
+        self
+    }
+
+    /// The activated outputs of the network's own [`voltages`](Self::voltages).
+    pub fn output(&self) -> Vec<f64> {
+        self.get_outputs(&self.voltages)
+    }
+
+    /// Estimate the largest Lyapunov exponent along the trajectory from the current voltages,
+    /// holding `inputs` constant.
+    /// > Uses Benettin's method: a copy perturbed by `1e-8` is ticked alongside, and after every
+    /// > tick their separation is logged and scaled back to `1e-8`. The estimate is negative when
+    /// > trajectories converge, near zero on a limit cycle, and positive for chaos. Parameters stay
+    /// > at their current values, since fluctuators only move in [`learn`](Self::learn), and the
+    /// > network's own voltages end up `steps` ticks further along
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(3);
+    /// assert!(ctrnn.largest_lyapunov(&[], 0.1, 100) < 0.0);
+    /// ```
+    pub fn largest_lyapunov(&mut self, inputs: &[f64], dt: f64, steps: usize) -> f64 {
+        const SEPARATION: f64 = 1e-8;
+        let mut perturbed = self.clone();
+        let offset = SEPARATION / (self.count as f64).sqrt();
+        for voltage in perturbed.voltages.iter_mut() { *voltage += offset; }
+
+        let mut growth = 0.0;
+        for _ in 0..steps {
+            self.tick(inputs, dt);
+            perturbed.tick(inputs, dt);
+            let distance = self.voltages.iter().zip(&perturbed.voltages)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            if distance == 0.0 { return f64::NEG_INFINITY; }
+
+            growth += (distance / SEPARATION).ln();
+            for (other, voltage) in perturbed.voltages.iter_mut().zip(&self.voltages) {
+                *other = voltage + (*other - voltage) * SEPARATION / distance;
+            }
+        }
+        growth / (steps as f64 * dt)
+    }
+
+    /// Run `steps` ticks with `inputs` held constant and list the nodes whose output variance over
+    /// that trajectory stays below `tol`, e.g. saturated or disconnected nodes that could be pruned.
+    /// > Like [`largest_lyapunov`](Self::largest_lyapunov), this advances the network's own voltages
+    pub fn dead_neurons(&mut self, inputs: &[f64], dt: f64, steps: usize, tol: f64) -> Vec<usize> {
+        let mut sums = vec![0.0; self.count];
+        let mut squares = vec![0.0; self.count];
+        for _ in 0..steps {
+            for (i, output) in self.tick(inputs, dt).output().into_iter().enumerate() {
+                sums[i] += output;
+                squares[i] += output * output;
+            }
+        }
+        let n = steps.max(1) as f64;
         (0..self.count)
-            .map(|i| voltages[i] + self.get_delta(&voltages, i) * dt + inputs.get(i).unwrap_or(&0.0))
+            .filter(|&i| squares[i] / n - (sums[i] / n).powi(2) < tol)
             .collect()
     }
 
-    pub fn get_outputs(&self, voltages: &Vec<f64>) -> Vec<f64> {
-        (0..self.count).map(|i| sigmoid(voltages[i] + self.biases[i].get())).collect()
+    /// Estimate the dominant frequency of `node`'s output by ticking `steps` times without inputs
+    /// and counting upward crossings of the output's mean.
+    /// > Crossing times are interpolated between ticks. `None` if the output crosses fewer than
+    /// > twice, e.g. when the network settles to a fixed point
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let mut ctrnn = ctrnn::RLCTRNN::new(2);
+    /// assert_eq!(ctrnn.estimate_frequency(0, 0.1, 500), None);
+    /// ```
+    pub fn estimate_frequency(&mut self, node: usize, dt: f64, steps: usize) -> Option<f64> {
+        self.check_node(node);
+        let trace: Vec<f64> = (0..steps).map(|_| self.tick(&[], dt).output()[node]).collect();
+        let mean = trace.iter().sum::<f64>() / steps as f64;
+
+        let crossings: Vec<f64> = trace.windows(2).enumerate()
+            .filter(|(_, pair)| pair[0] < mean && pair[1] >= mean)
+            .map(|(step, pair)| (step as f64 + (mean - pair[0]) / (pair[1] - pair[0])) * dt)
+            .collect();
+        match crossings[..] {
+            [first, .., last] => Some((crossings.len() - 1) as f64 / (last - first)),
+            _ => None,
+        }
     }
 
     pub fn init_voltage(&self) -> Vec<f64> {
         (0..self.count).map(|_| 0.0).collect()
     }
 
-    fn get_delta(&self, voltages: &Vec<f64>, index: usize) -> f64 {
+    fn try_node(&self, index: usize) -> Result<(), CtrnnError> {
+        if index < self.count { Ok(()) } else { Err(CtrnnError::NodeOutOfRange { index, count: self.count }) }
+    }
+
+    fn debug_assert_invariants(&self) {
+        debug_assert_eq!(self.biases.len(), self.count, "biases out of sync with count");
+        debug_assert_eq!(self.time_constants.len(), self.count, "time constants out of sync with count");
+        debug_assert_eq!(self.voltages.len(), self.count, "voltages out of sync with count");
+        debug_assert_eq!(self.weights.len(), self.count, "weight rows out of sync with count");
+        debug_assert!(self.weights.iter().all(|row| row.len() == self.count), "weight matrix is not square");
+    }
+
+    fn try_within(param: &Fluctuator, node: usize, value: f64) -> Result<(), CtrnnError> {
+        let Range { start, end } = param.range;
+        if (start..=end).contains(&value) { Ok(()) } else { Err(CtrnnError::OutOfBounds { node, value, range: start..end }) }
+    }
+
+    fn check_node(&self, index: usize) {
+        if let Err(error) = self.try_node(index) { panic!("{error}"); }
+    }
+
+    fn params_mut(&mut self) -> (impl Iterator<Item = &mut Fluctuator>, &mut StdRng) {
+        let params = self.biases.iter_mut()
+            .chain(self.time_constants.iter_mut())
+            .chain(self.weights.iter_mut().flatten());
+        (params, &mut self.rng)
+    }
+
+    /// Write `dV/dt` for every node into `out`.
+    fn derivative(&mut self, voltages: &[f64], min_tau: f64, out: &mut Vec<f64>) {
+        let mut activations = std::mem::take(&mut self.activations);
+        activations.clear();
+        activations.extend((0..self.count).map(|j| sigmoid(voltages[j] + self.biases[j].get())));
+
+        out.clear();
+        out.extend((0..self.count).map(|i| self.get_delta(&activations, voltages, min_tau, i)));
+        self.activations = activations;
+    }
+
+    fn get_delta(&self, activations: &[f64], voltages: &[f64], min_tau: f64, index: usize) -> f64 {
         let weights = &self.weights[index];
         let mut sum = 0.0;
         for j in 0..self.count {
-            let activation = sigmoid(voltages[j] + self.biases[j].get());
-            sum += weights[j].get() * activation
+            sum += weights[j].get() * activations[j]
         }
-        (sum - voltages[index]) / self.time_constants[index].get()
+        (sum - voltages[index]) / self.time_constants[index].get().max(min_tau)
     }
 }
 
@@ -139,4 +755,488 @@ mod test {
         assert_eq!(ctrnn.time_constants.len(), 6);
         assert_eq!(ctrnn.weights.len(), 6);
     }
+
+    #[test]
+    fn typed_weight_ends() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(Src(0), To(2), 1.0);
+        ctrnn.set_weight(1, 2, 2.0);
+        ctrnn.set_weight(Src(2), To(1), 3.0);
+        assert_eq!(ctrnn.weights[2][0].center, 1.0);
+        assert_eq!(ctrnn.weights[2][1].center, 2.0);
+        assert_eq!(ctrnn.weights[1][2].center, 3.0);
+        assert_eq!(ctrnn.weights[0][2].center, 0.0);
+    }
+
+    #[test]
+    fn update_into_matches_update() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 1, 2.0).set_weight(1, 2, -1.5).set_weight(2, 0, 0.5);
+        ctrnn.set_bias(1, -0.5).set_time_constant(2, 2.0);
+        let sigma = |v: f64, j: usize, ctrnn: &RLCTRNN| sigmoid(v + ctrnn.biases[j].get());
+
+        let mut voltages = vec![0.1, -0.2, 0.3];
+        let mut out = vec![];
+        for _ in 0..5 {
+            let expected: Vec<f64> = (0..3).map(|i| {
+                let sum: f64 = (0..3).map(|j| ctrnn.weights[i][j].get() * sigma(voltages[j], j, &ctrnn)).sum();
+                voltages[i] + (sum - voltages[i]) / ctrnn.time_constants[i].get() * 0.1 + if i == 0 { 0.5 } else { 0.0 }
+            }).collect();
+            let updated = ctrnn.update(0.1, &voltages, vec![0.5]);
+            ctrnn.update_into(0.1, &voltages, &[0.5], &mut out);
+            assert_eq!(updated, expected);
+            assert_eq!(out, expected);
+            voltages = out.clone();
+        }
+    }
+
+    #[test]
+    fn activations_once_per_stage() {
+        use crate::activation::test::SIGMOID_CALLS;
+
+        let stages = [(IntegrationMethod::Euler, 1), (IntegrationMethod::Heun, 2), (IntegrationMethod::RungeKutta4, 4)];
+        for (integration, evaluations) in stages {
+            let mut ctrnn = RLCTRNN::new(5);
+            ctrnn.integration = integration;
+            let voltages = ctrnn.init_voltage();
+            let mut out = vec![];
+
+            SIGMOID_CALLS.with(|calls| calls.set(0));
+            ctrnn.update_into(0.1, &voltages, &[], &mut out);
+            assert_eq!(SIGMOID_CALLS.with(|calls| calls.get()), evaluations * ctrnn.count, "{integration:?}");
+        }
+    }
+
+    fn centers(ctrnn: &RLCTRNN) -> Vec<f64> {
+        ctrnn.biases.iter()
+            .chain(ctrnn.time_constants.iter())
+            .chain(ctrnn.weights.iter().flatten())
+            .map(|param| param.center)
+            .collect()
+    }
+
+    #[test]
+    fn multi_objective_reward() {
+        let mut scalar = RLCTRNN::new(3);
+        scalar.weights.iter_mut().flatten().for_each(|weight| { weight.amplitude = 1.0; weight.time = 1.0; });
+        let mut combined = scalar.clone();
+        let mut reweighted = scalar.clone();
+        reweighted.set_reward_weight("energy", 0.0);
+
+        let reward = Reward::component("speed", 0.5).with("energy", 0.25);
+        scalar.learn(0.1, 0.75);
+        combined.learn(0.1, reward.clone());
+        reweighted.learn(0.1, reward);
+
+        assert_eq!(centers(&combined), centers(&scalar));
+        assert_ne!(centers(&reweighted), centers(&scalar));
+    }
+
+    #[test]
+    fn configure() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.weights[1][2].amplitude = 5.0;
+        ctrnn.configure(0.01..2.0, 4.0..5.0, 0.05, 0.2);
+        for param in ctrnn.params_mut().0 {
+            assert_eq!(param.range_amplitude, 0.01..2.0);
+            assert_eq!(param.range_period, 4.0..5.0);
+            assert_eq!(param.learning_rate, 0.05);
+            assert_eq!(param.convergence_rate, 0.2);
+            assert!((0.01..=2.0).contains(&param.amplitude));
+            assert!(param.range_period.contains(&param.period));
+        }
+        assert_eq!(ctrnn.weights[1][2].amplitude, 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "period range 0.01..0.5 must be non-empty and start at 0.1 or above")]
+    fn configure_short_periods() {
+        RLCTRNN::new(3).configure(0.02..0.08, 0.01..0.5, 0.1, 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "amplitude range 2.0..0.5 is empty")]
+    fn configure_reversed_amplitudes() {
+        RLCTRNN::new(3).configure(2.0..0.5, 4.0..8.0, 0.1, 0.1);
+    }
+
+    #[test]
+    fn randomized_time_constants() {
+        let mut ctrnn = RLCTRNN::new(200);
+        let fast = |ctrnn: &RLCTRNN| ctrnn.time_constants.iter().filter(|tau| tau.center < 0.1).count();
+        let slow = |ctrnn: &RLCTRNN| ctrnn.time_constants.iter().filter(|tau| tau.center > 1.0).count();
+
+        ctrnn.randomize_time_constants(0.01..10.0, true, 3);
+        assert!(ctrnn.time_constants.iter().all(|tau| tau.center > 0.0 && tau.center <= 10.0));
+        // Each decade holds about a third of the samples on a log axis
+        assert!(fast(&ctrnn) > 40 && slow(&ctrnn) > 40);
+
+        ctrnn.randomize_time_constants(0.01..10.0, false, 3);
+        assert!(ctrnn.time_constants.iter().all(|tau| (0.01..10.0).contains(&tau.center)));
+        assert!(fast(&ctrnn) < 10 && slow(&ctrnn) > 150);
+    }
+
+    #[test]
+    #[should_panic(expected = "time constant range 0.0..1.0 must be non-empty and positive")]
+    fn randomized_time_constants_from_zero() {
+        RLCTRNN::new(3).randomize_time_constants(0.0..1.0, false, 0);
+    }
+
+    #[test]
+    fn oscillator() {
+        let mut ctrnn = RLCTRNN::new(2);
+        ctrnn.set_weight(0, 0, 4.5).set_weight(1, 1, 4.5).set_weight(0, 1, -1.0).set_weight(1, 0, 1.0);
+        ctrnn.set_bias(0, -2.75).set_bias(1, -1.75);
+        for param in ctrnn.params_mut().0 { param.amplitude = 0.0; }
+
+        let mut voltages = ctrnn.init_voltage();
+        let mut outputs = vec![];
+        for _ in 0..2000 {
+            voltages = ctrnn.update(0.05, &voltages, vec![]);
+            outputs.push(ctrnn.get_outputs(&voltages)[0]);
+        }
+        let settled = &outputs[1000..];
+        assert!(settled.iter().any(|&output| output < 0.25));
+        assert!(settled.iter().any(|&output| output > 0.75));
+    }
+
+    #[test]
+    fn lyapunov() {
+        let mut stable = RLCTRNN::new(3);
+        stable.set_weight(0, 1, 0.5).set_weight(1, 2, -0.5);
+        stable.voltages = vec![1.0, -1.0, 0.5];
+        assert!(stable.largest_lyapunov(&[0.1], 0.1, 500) < -0.5);
+
+        let mut oscillator = RLCTRNN::new(2);
+        oscillator.set_weight(0, 0, 4.5).set_weight(1, 1, 4.5).set_weight(0, 1, -1.0).set_weight(1, 0, 1.0);
+        oscillator.set_bias(0, -2.75).set_bias(1, -1.75);
+        for param in oscillator.params_mut().0 { param.amplitude = 0.0; }
+        for _ in 0..2000 { oscillator.tick(&[], 0.05); }
+        let exponent = oscillator.largest_lyapunov(&[], 0.05, 20000);
+        assert!(exponent.abs() < 0.01, "{exponent}");
+    }
+
+    #[test]
+    fn dead_neurons() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 0, 4.5).set_weight(1, 1, 4.5).set_weight(0, 1, -1.0).set_weight(1, 0, 1.0);
+        ctrnn.set_bias(0, -2.75).set_bias(1, -1.75);
+        for param in ctrnn.params_mut().0 { param.amplitude = 0.0; }
+        assert_eq!(ctrnn.dead_neurons(&[], 0.05, 2000, 1e-6), vec![2]);
+    }
+
+    #[test]
+    fn oscillator_frequency() {
+        let mut ctrnn = RLCTRNN::new(2);
+        ctrnn.set_weight(0, 0, 4.5).set_weight(1, 1, 4.5).set_weight(0, 1, -1.0).set_weight(1, 0, 1.0);
+        ctrnn.set_bias(0, -2.75).set_bias(1, -1.75);
+        for param in ctrnn.params_mut().0 { param.amplitude = 0.0; }
+        ctrnn.settle(&[], 0.05, 2000);
+
+        // Integrating the limit cycle with a much finer step gives a period of 29.0
+        let frequency = ctrnn.estimate_frequency(0, 0.05, 20000).unwrap();
+        assert!((frequency * 29.0 - 1.0).abs() < 0.01, "{frequency}");
+        assert_eq!(RLCTRNN::new(2).estimate_frequency(0, 0.05, 2000), None);
+    }
+
+    #[test]
+    fn frozen_weight() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.weights.iter_mut().flatten().for_each(|weight| { weight.amplitude = 1.0; weight.time = 1.0; });
+        ctrnn.set_weight(0, 1, 0.5).set_weight_trainable(0, 1, false);
+        let before = ctrnn.clone();
+        for _ in 0..50 { ctrnn.learn(0.1, 0.01); }
+
+        assert_eq!(ctrnn.weights[1][0].center, 0.5);
+        for (to, row) in ctrnn.weights.iter().enumerate() {
+            for (from, weight) in row.iter().enumerate() {
+                if (from, to) != (0, 1) { assert_ne!(weight.center, before.weights[to][from].center); }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "node index 7 out of range for network of size 3")]
+    fn set_bias_out_of_range() {
+        RLCTRNN::new(3).set_bias(7, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "node index 3 out of range for network of size 3")]
+    fn set_time_constant_out_of_range() {
+        RLCTRNN::new(3).set_time_constant(3, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "node index 5 out of range for network of size 3")]
+    fn set_weight_out_of_range() {
+        RLCTRNN::new(3).set_weight(0, 5, 1.0);
+    }
+
+    #[test]
+    fn stateful_matches_explicit() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 1, 2.0).set_weight(1, 2, -1.5).set_weight(2, 0, 0.5).set_bias(1, -0.5);
+        let mut voltages = vec![0.1, -0.2, 0.3];
+        ctrnn.voltages = voltages.clone();
+        for _ in 0..10 {
+            voltages = ctrnn.update(0.1, &voltages, vec![0.5]);
+            ctrnn.tick(&[0.5], 0.1);
+            assert_eq!(ctrnn.voltages, voltages);
+            assert_eq!(ctrnn.output(), ctrnn.get_outputs(&voltages));
+        }
+
+        let buffers = (ctrnn.voltages.as_ptr(), ctrnn.next_voltages.as_ptr());
+        ctrnn.tick(&[0.5], 0.1).tick(&[0.5], 0.1);
+        assert_eq!((ctrnn.voltages.as_ptr(), ctrnn.next_voltages.as_ptr()), buffers);
+
+        ctrnn.add_node();
+        assert_eq!(ctrnn.voltages.len(), 4);
+    }
+
+    #[test]
+    fn settle() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 1, 2.0).set_weight(1, 2, -1.5).set_weight(2, 0, 0.5).set_bias(1, -0.5);
+        ctrnn.voltages = vec![1.0, -1.0, 0.5];
+        let change = |ctrnn: &mut RLCTRNN| {
+            let before = ctrnn.voltages.clone();
+            ctrnn.tick(&[0.2], 0.1);
+            before.iter().zip(&ctrnn.voltages).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt()
+        };
+
+        let first = change(&mut ctrnn.clone());
+        let settled = change(ctrnn.settle(&[0.2], 0.1, 300));
+        assert!(settled < first / 100.0, "{settled} vs {first}");
+    }
+
+    #[test]
+    fn sanitize() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 1, f64::NAN).set_weight(1, 2, 1.0);
+        let mut unsanitized = ctrnn.clone();
+        ctrnn.sanitize = true;
+
+        for step in 1..=20 {
+            ctrnn.tick(&[0.5], 0.1);
+            unsanitized.tick(&[0.5], 0.1);
+            assert!(ctrnn.voltages.iter().all(|voltage| voltage.is_finite()));
+            assert_eq!(ctrnn.nan_events(), step);
+        }
+        assert!(unsanitized.voltages[1].is_nan());
+        assert_eq!(unsanitized.nan_events(), 0);
+    }
+
+    #[test]
+    fn annealing() {
+        let mut consistent = RLCTRNN::new(3);
+        consistent.weights.iter_mut().flatten().for_each(|weight| weight.amplitude = 5.0);
+        consistent.enable_annealing(10, 10.0);
+        let mut noisy = consistent.clone();
+
+        for step in 0..20 {
+            consistent.learn(0.01, 0.1);
+            noisy.learn(0.01, if step % 2 == 0 { 0.4 } else { -0.2 });
+        }
+        let amplitude = |ctrnn: &RLCTRNN| ctrnn.weights[0][0].amplitude;
+        assert!((amplitude(&consistent) - 3.0).abs() < 1e-9);
+        assert!(amplitude(&noisy) > amplitude(&consistent) + 0.5);
+    }
+
+    #[test]
+    fn seeded_replay() {
+        let run = |seed| {
+            let mut ctrnn = RLCTRNN::with_seed(3, seed);
+            ctrnn.set_weight(0, 1, 1.0).add_node();
+            for _ in 0..500 { ctrnn.tick(&[1.0], 0.1).learn(0.1, 0.01); }
+            ctrnn.configure(0.01..2.0, 20.0..30.0, 0.1, 0.1);
+            let params: Vec<_> = ctrnn.params_mut().0.map(|param| (param.center, param.period)).collect();
+            params
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn integration_methods() {
+        let mut ctrnn = RLCTRNN::with_seed(2, 0);
+        ctrnn.set_weight(0, 0, -1.0).set_weight(0, 1, 2.0).set_weight(1, 0, -1.5).set_weight(1, 1, 0.5);
+        ctrnn.set_bias(0, 0.5).set_time_constant(1, 0.7);
+        for param in ctrnn.params_mut().0 { param.amplitude = 0.0; }
+
+        let mut run = |integration, dt: f64, steps| {
+            ctrnn.integration = integration;
+            ctrnn.voltages = vec![1.0, -1.0];
+            for _ in 0..steps { ctrnn.tick(&[], dt); }
+            ctrnn.voltages.clone()
+        };
+        let reference = run(IntegrationMethod::Euler, 0.0001, 40000);
+        let error = |voltages: Vec<f64>| -> f64 {
+            voltages.iter().zip(&reference).map(|(a, b)| (a - b).abs()).sum()
+        };
+        let euler = error(run(IntegrationMethod::Euler, 0.4, 10));
+        let heun = error(run(IntegrationMethod::Heun, 0.4, 10));
+        let rk4 = error(run(IntegrationMethod::RungeKutta4, 0.4, 10));
+        assert!(heun < euler);
+        assert!(rk4 < heun);
+    }
+
+    #[test]
+    fn bounded_weight() {
+        let mut ctrnn = RLCTRNN::new(2);
+        ctrnn.set_weight_range(0, 1, -2.0..2.0);
+        let weight = &mut ctrnn.weights[1][0];
+        weight.amplitude = 5.0;
+        weight.convergence_rate = 0.0;
+
+        for _ in 0..200 {
+            let weight = &ctrnn.weights[1][0];
+            let reward = if weight.get() > weight.center { 1.0 } else { -1.0 };
+            ctrnn.learn(0.1, reward);
+            assert!(ctrnn.weights[1][0].center <= 2.0);
+        }
+        assert_eq!(ctrnn.weights[1][0].center, 2.0);
+
+        ctrnn.set_weight(1, 0, 5.0).set_weight_range(1, 0, -1.0..1.0);
+        ctrnn.set_bias(1, -3.0).set_bias_range(1, -2.0..2.0);
+        assert_eq!(ctrnn.weight(1, 0), 1.0);
+        assert_eq!(ctrnn.bias(1), -2.0);
+    }
+
+    #[test]
+    fn vanishing_time_constant() {
+        for integration in [IntegrationMethod::Euler, IntegrationMethod::Heun, IntegrationMethod::RungeKutta4] {
+            let mut ctrnn = RLCTRNN::with_seed(3, 0);
+            ctrnn.integration = integration;
+            ctrnn.set_weight(0, 1, 3.0).set_weight(1, 2, -2.0).set_weight(2, 0, 1.5).set_weight(1, 1, 0.5);
+            ctrnn.weights.iter_mut().flatten().for_each(|weight| weight.amplitude = 0.0);
+            for tau in ctrnn.time_constants.iter_mut() {
+                tau.center = 0.05;
+                tau.amplitude = 0.1;
+            }
+            // Each target is a sigmoid-weighted sum, so it can't exceed the node's total incoming weight
+            let bounds: Vec<f64> = ctrnn.weights.iter()
+                .map(|row| row.iter().map(|weight| weight.center.abs()).sum())
+                .collect();
+
+            for _ in 0..5000 {
+                ctrnn.tick(&[], 0.1).learn(0.1, 0.0);
+                for (voltage, bound) in ctrnn.voltages.iter().zip(&bounds) {
+                    assert!(voltage.is_finite() && voltage.abs() <= bound + 1e-9, "{integration:?}: {voltage}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fallible_setters() {
+        let mut ctrnn = RLCTRNN::new(3);
+        let out_of_range = Err(CtrnnError::NodeOutOfRange { index: 3, count: 3 });
+        assert_eq!(ctrnn.try_set_bias(3, 1.0).map(|_| ()), out_of_range);
+        assert_eq!(ctrnn.try_set_time_constant(3, 1.0).map(|_| ()), out_of_range);
+        assert_eq!(ctrnn.try_set_weight(3, 0, 1.0).map(|_| ()), out_of_range);
+        assert_eq!(ctrnn.try_set_weight(0, 3, 1.0).map(|_| ()), out_of_range);
+        assert_eq!(
+            ctrnn.try_set_time_constant(1, 0.0).map(|_| ()),
+            Err(CtrnnError::InvalidTimeConstant { node: 1, value: 0.0 }),
+        );
+        assert!(ctrnn.try_set_bias(0, f64::NAN).is_err());
+        assert!(ctrnn.try_set_weight(0, 1, f64::INFINITY).is_err());
+        assert_eq!(
+            ctrnn.try_set_weight(0, 1, 20.0).map(|_| ()),
+            Err(CtrnnError::OutOfBounds { node: 1, value: 20.0, range: -16.0..16.0 }),
+        );
+        assert!(ctrnn.try_set_bias(0, -17.0).is_err());
+        assert!(ctrnn.try_set_time_constant(0, 16.5).is_err());
+        assert_eq!(ctrnn.weight(0, 1), 0.0);
+
+        ctrnn.try_set_bias(0, 1.5).unwrap()
+            .try_set_time_constant(1, 2.5).unwrap()
+            .try_set_weight(0, 2, -0.5).unwrap();
+        assert_eq!(ctrnn.bias(0), 1.5);
+        assert_eq!(ctrnn.time_constant(1), 2.5);
+        assert_eq!(ctrnn.weight(0, 2), -0.5);
+        assert_eq!(ctrnn.weight(2, 0), 0.0);
+    }
+
+    #[test]
+    fn clear_weights() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 1, 2.0).set_weight(2, 2, -1.0).set_bias(1, 0.5).set_time_constant(2, 3.0);
+        ctrnn.voltages = vec![0.1, 0.2, 0.3];
+        let before = ctrnn.clone();
+
+        ctrnn.clear_weights();
+        for from in 0..3 {
+            for to in 0..3 { assert_eq!(ctrnn.weight(from, to), 0.0); }
+        }
+        for i in 0..3 {
+            assert_eq!(ctrnn.bias(i), before.bias(i));
+            assert_eq!(ctrnn.time_constant(i), before.time_constant(i));
+        }
+        assert_eq!(ctrnn.voltages, before.voltages);
+    }
+
+    #[test]
+    fn normalize_weights() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.set_weight(0, 1, 3.0).set_weight(1, 2, -1.0).set_weight(2, 0, 1.0);
+        assert_eq!(ctrnn.weight_bounds(), (-1.0, 3.0));
+
+        ctrnn.normalize_weights(-0.5..0.5);
+        assert_eq!(ctrnn.weight_bounds(), (-0.5, 0.5));
+        assert_eq!(ctrnn.weight(0, 1), 0.5);
+        assert_eq!(ctrnn.weight(1, 2), -0.5);
+        assert_eq!(ctrnn.weight(2, 0), 0.0);
+
+        let mut constant = RLCTRNN::new(3);
+        constant.normalize_weights(1.0..2.0);
+        assert!(constant.weights.iter().flatten().all(|weight| weight.center == 1.5));
+    }
+
+    #[test]
+    fn unreachable_outputs() {
+        let mut ctrnn = RLCTRNN::new(6);
+        ctrnn.set_weight(0, 2, 1.0).set_weight(2, 3, -0.5).set_weight(3, 2, 2.0).set_weight(5, 4, 1.0);
+        assert_eq!(ctrnn.unreachable_outputs(&[0, 1], &[3, 4]), vec![4]);
+        assert_eq!(ctrnn.unreachable_outputs(&[0, 1], &[1]), Vec::<usize>::new());
+        assert_eq!(ctrnn.unreachable_outputs(&[0, 5], &[3, 4]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn default_time_constants_stay_positive() {
+        let mut ctrnn = RLCTRNN::new(3);
+        let longest = ctrnn.time_constants[0].range_period.end;
+        for _ in 0..(longest / 0.1) as usize + 1 {
+            assert!(ctrnn.time_constants.iter().all(|tau| tau.get() > 0.0));
+            ctrnn.tick(&[0.5], 0.1).learn(0.1, 0.0);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn invariants() {
+        let mut ctrnn = RLCTRNN::new(2);
+        ctrnn.add_node().set_weight(2, 0, 1.0).add_node().configure(0.01..2.0, 4.0..8.0, 0.1, 0.1);
+        ctrnn.randomize_time_constants(0.5..2.0, true, 0).clear_weights().normalize_weights(-1.0..1.0);
+        ctrnn.settle(&[0.5], 0.1, 10).learn(0.1, 0.5);
+        ctrnn.debug_assert_invariants();
+        assert_eq!(ctrnn.count, 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "voltages out of sync with count")]
+    fn broken_invariants() {
+        let mut ctrnn = RLCTRNN::new(3);
+        ctrnn.voltages.pop();
+        ctrnn.learn(0.1, 0.0);
+    }
+
+    #[test]
+    fn thread_safety() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RLCTRNN>();
+        assert_send_sync::<Fluctuator>();
+    }
 }